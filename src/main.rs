@@ -1,16 +1,22 @@
 mod csv_parser {
     use nom::{
-        bytes::complete::is_not,
-        character::complete::{char, newline},
+        branch::alt,
+        bytes::complete::{is_not, tag},
+        character::complete::char,
         multi::separated_list1,
         IResult,
     };
+    use nom::error::{Error, ErrorKind};
+    use std::collections::HashMap;
+    use std::io::Write;
 
     #[derive(Debug, PartialEq)]
     pub enum ParseError {
         ParseIntError(std::num::ParseIntError),
         IndexOutOfBounds,
         CsvParseError,
+        NonNumericCell(String),
+        UnknownColumn(String),
     }
 
     impl From<std::num::ParseIntError> for ParseError {
@@ -25,6 +31,111 @@ mod csv_parser {
         }
     }
 
+    ///
+    ///                      CsvOptions
+    ///---------------------------------------------------------
+    ///Configures how the parsing and writing functions handle a CSV-like input:
+    ///- `delimiter`: the character separating fields on a line (defaults to `,`).
+    ///- `has_headers`: whether row 0 is a header and should be skipped by functions
+    ///  that aggregate over data rows, such as the `_sum_` family (defaults to `false`).
+    ///- `trim`: whether leading/trailing whitespace is stripped from each field when
+    ///  parsing (defaults to `false`).
+    ///- `line_ending`: the sequence `write_csv` joins rows with (defaults to `"\n"`).
+    ///  Parsing always accepts `\r\n`, `\n`, or `\r` regardless of this setting; `line_ending`
+    ///  is restricted to those same three sequences (see `CsvOptions::line_ending`) so that
+    ///  the rows `write_csv` produces are always split back into records by `parse_csv`.
+    ///
+    ///Built with the builder methods below, e.g.:
+    ///```ignore
+    ///let opts = CsvOptions::new().delimiter(';').has_headers(true).trim(true);
+    ///```
+    ///
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct CsvOptions {
+        delimiter: char,
+        has_headers: bool,
+        trim: bool,
+        line_ending: String,
+    }
+
+    impl Default for CsvOptions {
+        fn default() -> Self {
+            CsvOptions {
+                delimiter: ',',
+                has_headers: false,
+                trim: false,
+                line_ending: "\n".to_string(),
+            }
+        }
+    }
+
+    impl CsvOptions {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn delimiter(mut self, delimiter: char) -> Self {
+            self.delimiter = delimiter;
+            self
+        }
+
+        pub fn has_headers(mut self, has_headers: bool) -> Self {
+            self.has_headers = has_headers;
+            self
+        }
+
+        /// Unrecognized sequences are ignored (the previous value is kept) rather than
+        /// accepted, since `parse_csv`/`parse_csv_with` only ever split records on `\r\n`,
+        /// `\n`, or `\r` (see `line_ending_any`); anything else would make `write_csv`'s
+        /// output write-only.
+        pub fn line_ending(mut self, line_ending: &str) -> Self {
+            if matches!(line_ending, "\r\n" | "\n" | "\r") {
+                self.line_ending = line_ending.to_string();
+            }
+            self
+        }
+
+        pub fn trim(mut self, trim: bool) -> Self {
+            self.trim = trim;
+            self
+        }
+    }
+
+    ///
+    ///                      CsvValue
+    ///---------------------------------------------------------
+    ///A single CSV cell, inferred as the most specific type it parses as: an `Int` if the
+    ///whole field parses as `i64`, otherwise a `Float` if it parses as a finite `f64`, otherwise
+    ///the raw `Text` (so "nan" and "inf" stay `Text`). Produced by `parse_csv_typed`.
+    ///
+    #[derive(Debug, PartialEq, Clone)]
+    pub enum CsvValue {
+        Int(i64),
+        Float(f64),
+        Text(String),
+    }
+
+    ///
+    ///                      infer_value
+    ///---------------------------------------------------------
+    ///Input: A single field as owned `String` (already unquoted/trimmed by `parse_field`).
+    ///
+    ///Output: The field typed as an `Int` or a finite `Float` when it parses as one, or `Text` otherwise.
+    ///
+    fn infer_value(field: String) -> CsvValue {
+        if let Ok(i) = field.parse::<i64>() {
+            CsvValue::Int(i)
+        } else if let Ok(f) = field.parse::<f64>() {
+            if f.is_finite() {
+                CsvValue::Float(f)
+            } else {
+                CsvValue::Text(field) // Reject "nan"/"inf"/"infinity" spellings: genuinely textual, not numeric
+            }
+        } else {
+            CsvValue::Text(field)
+        }
+    }
+
     ///
     ///                      parse_csv
     ///---------------------------------------------------------
@@ -44,13 +155,76 @@ mod csv_parser {
     ///  Example output in case of failure:
     ///  Err("Error while parsing the CSV".to_string())
     ///
+    ///Records are split on a line ending (`\r\n`, `\n`, or `\r`, see `line_ending_any`), but
+    ///one inside a quoted field (see `parse_field`) is consumed as part of that field rather
+    ///than treated as a record boundary, since `parse_record` only ever stops on an unquoted
+    ///separator.
+    ///
+    ///Uses the default `CsvOptions` (comma-delimited, no header skipping, no trimming).
+    ///Use `parse_csv_with` to customize the delimiter or enable trimming.
+    ///
     pub fn parse_csv(input: &str) -> Result<Vec<Vec<String>>, ParseError> {
-        match separated_list1(newline, parse_record)(input) {
-            Ok((_, records)) => Ok(records), // Return successfully parsed lines
+        parse_csv_with(input, &CsvOptions::default())
+    }
+
+    ///
+    ///                      parse_csv_with
+    ///---------------------------------------------------------
+    ///Same as `parse_csv`, but the delimiter and per-field trimming are taken from `opts`
+    ///instead of the hardcoded `,` default. `opts.has_headers` has no effect here (the
+    ///header row is still returned as part of the result); it only affects the functions
+    ///that aggregate over data rows, such as `parse_and_sum_col_from_csv_with`.
+    ///
+    ///Any input left unconsumed after the last record (other than a single trailing line
+    ///ending) is treated as a parse error rather than silently discarded, so malformed
+    ///input such as a quoted field followed by stray characters doesn't truncate the rest
+    ///of the document without warning.
+    ///
+    pub fn parse_csv_with(input: &str, opts: &CsvOptions) -> Result<Vec<Vec<String>>, ParseError> {
+        match separated_list1(line_ending_any, |i| parse_record(i, opts))(input) {
+            Ok((remaining, records)) => {
+                let fully_consumed = remaining.is_empty()
+                    || matches!(line_ending_any(remaining), Ok((after_ending, _)) if after_ending.is_empty());
+                if fully_consumed {
+                    Ok(records) // Return successfully parsed lines
+                } else {
+                    Err(ParseError::CsvParseError) // Unparsed leftover after the last record
+                }
+            }
             Err(_) => Err(ParseError::CsvParseError), // Error handling if parsing fails
         }
     }
 
+    ///
+    ///                      line_ending_any
+    ///---------------------------------------------------------
+    ///Input: The remaining CSV input, right after a record.
+    ///
+    ///Output: `IResult<&str, &str>`
+    ///- Matches a Windows (`\r\n`), Unix (`\n`) or classic Mac (`\r`) line ending,
+    ///  whichever is present, so records separated by any of the three split the same way.
+    ///
+    fn line_ending_any(input: &str) -> IResult<&str, &str> {
+        alt((tag("\r\n"), tag("\n"), tag("\r")))(input)
+    }
+
+    ///
+    ///                      parse_csv_typed
+    ///---------------------------------------------------------
+    ///Same as `parse_csv_with`, but each field is additionally inferred into a `CsvValue`
+    ///(`Int`, `Float`, or `Text`) instead of staying a plain `String`. This lets a column of
+    ///decimals such as heights (`74.5`) be summed, which the plain string-based functions
+    ///couldn't do.
+    ///
+    pub fn parse_csv_typed(input: &str, opts: &CsvOptions) -> Result<Vec<Vec<CsvValue>>, ParseError> {
+        parse_csv_with(input, opts).map(|records| {
+            records
+                .into_iter()
+                .map(|line| line.into_iter().map(infer_value).collect())
+                .collect()
+        })
+    }
+
     ///
     ///                      parse_record
     ///---------------------------------------------------------
@@ -63,45 +237,80 @@ mod csv_parser {
     ///  Example output:
     ///  Ok(("", vec!["Alex", "M", "41", "74", "170"]))
     ///
-    fn parse_record(input: &str) -> IResult<&str, Vec<String>> {
-        separated_list1(char(','), parse_field)(input) // Split a CSV line into fields based on commas
+    fn parse_record<'a>(input: &'a str, opts: &CsvOptions) -> IResult<&'a str, Vec<String>> {
+        separated_list1(char(opts.delimiter), |i| parse_field(i, opts))(input) // Split a CSV line into fields based on `opts.delimiter`
     }
 
     ///
     ///                      parse_field
     ///---------------------------------------------------------
     ///Input: A single field as `&str`.
-    ///Examples of input: "Alex", " 41 ", "Weight"
+    ///Examples of input: "Alex", "41", "\"Smith, John\"", "\"She said \"\"hi\"\"\""
     ///
     ///Output: `IResult<&str, String>`
     ///- `&str`: Unparsed remaining part (after extracting a field).
-    ///- `String`: Cleaned field containing only alphanumeric characters.
+    ///- `String`: The field's content. A quoted field (RFC 4180 style) is unwrapped and
+    ///  any doubled `""` inside it is collapsed to a literal `"`, verbatim and never
+    ///  trimmed (quoting is how a field asks for its whitespace to be kept); an unquoted
+    ///  field is taken as-is, up to the next `opts.delimiter` or line ending (`\r`/`\n`),
+    ///  then optionally trimmed if `opts.trim` is set.
     ///  Example output:
     ///  Ok(("", "Alex".to_string()))
+    ///  Ok(("", "Smith, John".to_string()))
+    ///
+    ///A closing quote must be immediately followed by `opts.delimiter`, a line ending, or
+    ///the end of input; anything else (e.g. `"foo"garbage`) is rejected rather than
+    ///silently accepted, which would otherwise leave the rest of the document unconsumed.
     ///
-    fn parse_field(input: &str) -> IResult<&str, String> {
-        let (next_input, field) = is_not(",\n")(input)?; // Read until the next comma or newline
-        let cleaned_field = clean_field(field); // Clean the field (e.g., remove spaces, special characters)
-        Ok((next_input, cleaned_field))
+    fn parse_field<'a>(input: &'a str, opts: &CsvOptions) -> IResult<&'a str, String> {
+        if input.starts_with('"') {
+            let (next_input, field) = parse_quoted_field(input)?;
+            if !next_input.is_empty()
+                && !next_input.starts_with(opts.delimiter)
+                && !next_input.starts_with('\n')
+                && !next_input.starts_with('\r')
+            {
+                return Err(nom::Err::Error(Error::new(next_input, ErrorKind::Tag))); // Stray data after the closing quote
+            }
+            Ok((next_input, field)) // opts.trim never applies inside quotes: quoting exists to preserve the content verbatim
+        } else {
+            let terminators: String = [opts.delimiter, '\n', '\r'].iter().collect();
+            let (next_input, field) = is_not(terminators.as_str())(input)?; // Read until the next delimiter or line ending
+            let field = if opts.trim { field.trim().to_string() } else { field.to_string() };
+            Ok((next_input, field))
+        }
     }
 
     ///
-    ///                      clean_field
+    ///                      parse_quoted_field
     ///---------------------------------------------------------
-    ///Input: A string representing a CSV field.
-    ///Example input: " Alex ", " 41 ", "Carl!"
+    ///Input: A field starting with an opening `"`, e.g. `"\"Smith, John\",41"`.
     ///
-    ///Output: A cleaned string containing only alphanumeric characters.
-    ///Example output:
-    ///- "Alex" becomes "Alex"
-    ///- "     41 " becomes "41"
-    ///- "Carl!" becomes "Carl"
-    ///
-    fn clean_field(field: &str) -> String {
-        field
-            .chars()
-            .filter(|c| c.is_alphanumeric()) // Keep only alphanumeric characters
-            .collect()
+    ///Output: `IResult<&str, String>`
+    ///- `&str`: The remaining input right after the closing `"` (the delimiter or
+    ///  record separator that follows is left untouched).
+    ///- `String`: The unescaped contents between the quotes, with every `""` turned
+    ///  into a single `"` and the delimiter/newline inside the quotes kept as literal data.
+    ///
+    fn parse_quoted_field(input: &str) -> IResult<&str, String> {
+        let (mut rest, _) = char('"')(input)?;
+        let mut field = String::new();
+
+        loop {
+            match rest.find('"') {
+                None => return Err(nom::Err::Error(Error::new(rest, ErrorKind::Tag))), // Unterminated quoted field
+                Some(idx) => {
+                    field.push_str(&rest[..idx]);
+                    rest = &rest[idx + 1..];
+                    if let Some(after_quote) = rest.strip_prefix('"') {
+                        field.push('"'); // Doubled quote `""` is an escaped literal `"`
+                        rest = after_quote;
+                    } else {
+                        return Ok((rest, field)); // Lone `"` closes the field
+                    }
+                }
+            }
+        }
     }
 
     ///
@@ -151,8 +360,20 @@ mod csv_parser {
     ///  Example output for `line_number = 1`: "Bert, M, 42, 68, 166"
     ///- Err(String): An error message if the line doesn't exist or if parsing fails.
     ///
+    ///Uses the default `CsvOptions`. Use `parse_and_get_line_from_csv_with` to customize
+    ///the delimiter or trimming.
+    ///
     pub fn parse_and_get_line_from_csv(input: &str, line_number: usize) -> Result<String, ParseError> {
-        match parse_csv(input) {
+        parse_and_get_line_from_csv_with(input, line_number, &CsvOptions::default())
+    }
+
+    ///
+    ///                      parse_and_get_line_from_csv_with
+    ///---------------------------------------------------------
+    ///Same as `parse_and_get_line_from_csv`, but parses `input` with the given `opts`.
+    ///
+    pub fn parse_and_get_line_from_csv_with(input: &str, line_number: usize, opts: &CsvOptions) -> Result<String, ParseError> {
+        match parse_csv_with(input, opts) {
             Ok(records) => records
                 .get(line_number)
                 .map(|line| line.join(", ")) // Join fields with commas
@@ -173,8 +394,22 @@ mod csv_parser {
     ///  Example output for `col_number = 0`: ["Name", "Alex", "Bert", "Carl", "Dave", ... ,"Ruth"]
     ///- Err(String): An error message if the column doesn't exist or if parsing fails.
     ///
+    ///Uses the default `CsvOptions`. Use `parse_and_get_col_from_csv_with` to customize
+    ///the delimiter or trimming.
+    ///
     pub fn parse_and_get_col_from_csv(input: &str, col_number: usize) -> Result<Vec<String>, ParseError> {
-        match parse_csv(input) {
+        parse_and_get_col_from_csv_with(input, col_number, &CsvOptions::default())
+    }
+
+    ///
+    ///                      parse_and_get_col_from_csv_with
+    ///---------------------------------------------------------
+    ///Same as `parse_and_get_col_from_csv`, but parses `input` with the given `opts` and
+    ///skips the header row when `opts.has_headers` is `true`, consistent with
+    ///`parse_and_sum_col_from_csv_with`.
+    ///
+    pub fn parse_and_get_col_from_csv_with(input: &str, col_number: usize, opts: &CsvOptions) -> Result<Vec<String>, ParseError> {
+        match parse_csv_with(input, opts) {
             Ok(records) => {
                 let mut column = Vec::new();
                 for line in records {
@@ -183,7 +418,10 @@ mod csv_parser {
                     } else {
                         return Err(ParseError::IndexOutOfBounds);
                     }
-                    
+
+                }
+                if opts.has_headers {
+                    column.remove(0); // Ignore the header row
                 }
                 Ok(column)
             }
@@ -204,21 +442,239 @@ mod csv_parser {
     ///  Example output for `col_number = 4`: 2641
     ///- Err(String): An error message if the column doesn't exist, if parsing fails or if the column is composed by non-digit.
     ///
-    pub fn parse_and_sum_col_from_csv(input: &str, col_number: usize) -> Result<i32, ParseError> {
-        match parse_and_get_col_from_csv(input, col_number) {
-            Ok(column) => {
-                Ok(column
-                    .iter() // For each element (string) of the column
-                    .skip(1) // To ignore column header
-                    .map(|value| value.parse::<i32>().map_err(ParseError::ParseIntError)) // Convert in i32
-                    .collect::<Result<Vec<i32>, ParseError>>()? // Collect as a i32 vector or return an error
-                    .iter() // For each element (i32) of the column, except the header
-                    .sum::<i32>() // Sum each number
-                )
+    ///Sums both `Int` and `Float` cells of the column (see `CsvValue`), so a column of
+    ///decimals like heights (`74.5`) can be summed, not just whole numbers.
+    ///Assumes a header row is present and skips it (`CsvOptions::default().has_headers(true)`).
+    ///Use `parse_and_sum_col_from_csv_with` to control this (and the delimiter/trimming)
+    ///via `opts.has_headers`.
+    ///
+    pub fn parse_and_sum_col_from_csv(input: &str, col_number: usize) -> Result<CsvValue, ParseError> {
+        parse_and_sum_col_from_csv_with(input, col_number, &CsvOptions::default().has_headers(true))
+    }
+
+    ///
+    ///                      parse_and_sum_col_from_csv_with
+    ///---------------------------------------------------------
+    ///Same as `parse_and_sum_col_from_csv`, but parses `input` with the given `opts` and
+    ///skips the column header only when `opts.has_headers` is `true`, instead of always
+    ///ignoring the first row.
+    ///
+    ///Output: `Result<CsvValue, ParseError>`
+    ///- Ok(CsvValue::Int(sum)): The column is made of whole numbers only.
+    ///- Ok(CsvValue::Float(sum)): The column contains at least one decimal cell.
+    ///- Err(ParseError::NonNumericCell(cell)): A genuinely textual cell was found in the column.
+    ///- Err(ParseError::IndexOutOfBounds): The column doesn't exist.
+    ///
+    pub fn parse_and_sum_col_from_csv_with(input: &str, col_number: usize, opts: &CsvOptions) -> Result<CsvValue, ParseError> {
+        match parse_csv_typed(input, opts) {
+            Ok(records) => {
+                let mut column = Vec::new();
+                for line in &records {
+                    match line.get(col_number) {
+                        Some(value) => column.push(value.clone()),
+                        None => return Err(ParseError::IndexOutOfBounds),
+                    }
+                }
+                if opts.has_headers {
+                    column.remove(0); // Ignore the header row
+                }
+                sum_typed_column(&column)
             }
             Err(e) => Err(e),
         }
     }
+
+    ///
+    ///                      sum_typed_column
+    ///---------------------------------------------------------
+    ///Input: A column of `CsvValue` (typically from `parse_csv_typed`, with the header
+    ///already removed if there was one).
+    ///
+    ///Output: `Result<CsvValue, ParseError>`
+    ///- Ok(CsvValue::Int(sum)): Every cell was an `Int`.
+    ///- Ok(CsvValue::Float(sum)): At least one cell was a `Float` (ints are promoted).
+    ///- Err(ParseError::NonNumericCell(cell)): A `Text` cell was found.
+    ///
+    fn sum_typed_column(column: &[CsvValue]) -> Result<CsvValue, ParseError> {
+        let mut int_sum: i64 = 0;
+        let mut float_sum: f64 = 0.0;
+        let mut has_float = false;
+
+        for value in column {
+            match value {
+                CsvValue::Int(i) => {
+                    int_sum += i;
+                    float_sum += *i as f64;
+                }
+                CsvValue::Float(f) => {
+                    has_float = true;
+                    float_sum += f;
+                }
+                CsvValue::Text(text) => return Err(ParseError::NonNumericCell(text.clone())),
+            }
+        }
+
+        Ok(if has_float {
+            CsvValue::Float(float_sum)
+        } else {
+            CsvValue::Int(int_sum)
+        })
+    }
+
+    ///
+    ///                      header_index_map
+    ///---------------------------------------------------------
+    ///Input: The header row (first record) of a parsed CSV.
+    ///
+    ///Output: A map from header name to its column index, e.g. for
+    ///`["Name", "Sex", "Age"]`: `{"Name": 0, "Sex": 1, "Age": 2}`.
+    ///
+    fn header_index_map(header: &[String]) -> HashMap<String, usize> {
+        header
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (name.clone(), index))
+            .collect()
+    }
+
+    ///
+    ///                      column_index_by_name
+    ///---------------------------------------------------------
+    ///Input:
+    ///- `input`: CSV content as `&str`, whose first row is treated as the header.
+    ///- `name`: The header to look up.
+    ///- `opts`: Parsing options (delimiter, trimming).
+    ///
+    ///Output: `Result<usize, ParseError>`
+    ///- Ok(usize): The column index matching `name`.
+    ///- Err(ParseError::UnknownColumn(name)): No header matches `name`.
+    ///- Err(ParseError): Any error from parsing `input`.
+    ///
+    fn column_index_by_name(input: &str, name: &str, opts: &CsvOptions) -> Result<usize, ParseError> {
+        let records = parse_csv_with(input, opts)?;
+        let header = records.first().ok_or(ParseError::CsvParseError)?;
+        header_index_map(header)
+            .get(name)
+            .copied()
+            .ok_or_else(|| ParseError::UnknownColumn(name.to_string()))
+    }
+
+    ///
+    ///                      parse_and_get_col_by_name
+    ///---------------------------------------------------------
+    ///Input:
+    ///- `input`: CSV content as `&str`, whose first row is treated as the header.
+    ///- `name`: The header of the column to retrieve, e.g. "Weightlbs".
+    ///
+    ///Output: `Result<Vec<String>, ParseError>`
+    ///- Ok(Vec<String>): The retrieved column, header included (same shape as
+    ///  `parse_and_get_col_from_csv`).
+    ///- Err(ParseError::UnknownColumn(name)): No header matches `name`.
+    ///
+    ///Uses the default `CsvOptions`. Use `parse_and_get_col_by_name_with` to customize
+    ///the delimiter or trimming.
+    ///
+    pub fn parse_and_get_col_by_name(input: &str, name: &str) -> Result<Vec<String>, ParseError> {
+        parse_and_get_col_by_name_with(input, name, &CsvOptions::default())
+    }
+
+    ///
+    ///                      parse_and_get_col_by_name_with
+    ///---------------------------------------------------------
+    ///Same as `parse_and_get_col_by_name`, but parses `input` with the given `opts`.
+    ///
+    pub fn parse_and_get_col_by_name_with(input: &str, name: &str, opts: &CsvOptions) -> Result<Vec<String>, ParseError> {
+        let col_number = column_index_by_name(input, name, opts)?;
+        parse_and_get_col_from_csv_with(input, col_number, opts)
+    }
+
+    ///
+    ///                      sum_col_by_name
+    ///---------------------------------------------------------
+    ///Input:
+    ///- `input`: CSV content as `&str`, whose first row is treated as the header.
+    ///- `name`: The header of the column to sum, e.g. "Weightlbs".
+    ///
+    ///Output: Same as `parse_and_sum_col_from_csv`, looked up by header name instead of index.
+    ///
+    ///Assumes a header row is present (`CsvOptions::default().has_headers(true)`), since a
+    ///header is required to resolve `name` in the first place. Use `sum_col_by_name_with`
+    ///to customize the delimiter or trimming.
+    ///
+    pub fn sum_col_by_name(input: &str, name: &str) -> Result<CsvValue, ParseError> {
+        sum_col_by_name_with(input, name, &CsvOptions::default().has_headers(true))
+    }
+
+    ///
+    ///                      sum_col_by_name_with
+    ///---------------------------------------------------------
+    ///Same as `sum_col_by_name`, but parses `input` with the given `opts`.
+    ///
+    pub fn sum_col_by_name_with(input: &str, name: &str, opts: &CsvOptions) -> Result<CsvValue, ParseError> {
+        let col_number = column_index_by_name(input, name, opts)?;
+        parse_and_sum_col_from_csv_with(input, col_number, opts)
+    }
+
+    ///
+    ///                      write_csv
+    ///---------------------------------------------------------
+    ///Input:
+    ///- `records`: Rows of fields to serialize, e.g. the output of `parse_csv`.
+    ///- `opts`: The delimiter and line ending to join with (see `CsvOptions`).
+    ///
+    ///Output: A `String` containing valid CSV text, the inverse of `parse_csv_with`:
+    ///fields are joined with `opts.delimiter` and rows with `opts.line_ending`. Any field
+    ///containing the delimiter, a `"`, `\r`, or `\n` is wrapped in double quotes, with
+    ///internal `"` doubled per RFC 4180. Empty fields are written unquoted (nothing between
+    ///two delimiters), which `parse_csv_with` rejects, so a record holding an empty field does
+    ///not read back; likewise an empty record, or an empty `records`.
+    ///  Example output for `opts` default and `records = vec![vec!["Smith, John".into(), "41".into()]]`:
+    ///  "\"Smith, John\",41"
+    ///
+    pub fn write_csv(records: &[Vec<String>], opts: &CsvOptions) -> String {
+        records
+            .iter()
+            .map(|record| {
+                record
+                    .iter()
+                    .map(|field| write_field(field, opts))
+                    .collect::<Vec<String>>()
+                    .join(&opts.delimiter.to_string())
+            })
+            .collect::<Vec<String>>()
+            .join(&opts.line_ending)
+    }
+
+    ///
+    ///                      write_csv_to
+    ///---------------------------------------------------------
+    ///Same as `write_csv`, but writes the resulting CSV text to `writer` instead of
+    ///returning it as a `String`.
+    ///
+    pub fn write_csv_to<W: Write>(writer: &mut W, records: &[Vec<String>], opts: &CsvOptions) -> std::io::Result<()> {
+        write!(writer, "{}", write_csv(records, opts))
+    }
+
+    ///
+    ///                      write_field
+    ///---------------------------------------------------------
+    ///Input: A single field and the options it should be written with.
+    ///
+    ///Output: The field as-is if it needs no escaping, or wrapped in double quotes with
+    ///every internal `"` doubled if it contains `opts.delimiter`, `"`, `\r`, or `\n`.
+    ///
+    fn write_field(field: &str, opts: &CsvOptions) -> String {
+        let needs_quoting = field.contains(opts.delimiter)
+            || field.contains('"')
+            || field.contains('\r')
+            || field.contains('\n');
+
+        if needs_quoting {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
 }
 
 
@@ -226,11 +682,17 @@ fn main() {
     use crate::csv_parser::{
         parse_and_sum_col_from_csv,
         parse_and_get_col_from_csv,
+        parse_and_get_col_by_name,
         parse_and_get_line_from_csv,
         parse_csv,
-        pretty_print_csv
+        parse_csv_with,
+        pretty_print_csv,
+        sum_col_by_name,
+        write_csv_to,
+        CsvOptions,
     };
     use std::fs;
+    use std::io;
 
     // Load the CSV file
     let file_path = "biostats1.csv";
@@ -263,6 +725,12 @@ fn main() {
         Err(err) => eprintln!("Error: {:?}", err),
     }
 
+    // Getting the "Sex" column, looked up by header name instead of index
+    match parse_and_get_col_by_name(&csv_content, "Sex") {
+        Ok(column) => println!("Column \"Sex\": {:?}", column),
+        Err(err) => eprintln!("Error: {:?}", err),
+    }
+
     // Sum the 5th column (weigth) of the CSV file
     let col_to_sum = 4;
     match parse_and_sum_col_from_csv(&csv_content, col_to_sum) {
@@ -270,6 +738,30 @@ fn main() {
         Err(err) => eprintln!("Error: {:?}", err),
     }
 
+    // Same sum, looked up by header name instead of index
+    match sum_col_by_name(&csv_content, "Weightlbs") {
+        Ok(sum) => println!("Sum of the column \"Weightlbs\": {:?}", sum),
+        Err(err) => eprintln!("Error: {:?}", err),
+    }
+
+    // A semicolon-separated, trimmed variant of the same file parses the same way
+    let semicolon_csv = "Name; Sex; Age\nAlex; M; 41\nBert; M; 42\n";
+    let semicolon_opts = CsvOptions::new()
+        .delimiter(';')
+        .has_headers(true)
+        .trim(true)
+        .line_ending("\r\n");
+    match parse_csv_with(semicolon_csv, &semicolon_opts) {
+        Ok(records) => {
+            println!("Semicolon CSV: {:?}", records);
+            // Write it back out with its own options, to standard output
+            if let Err(err) = write_csv_to(&mut io::stdout(), &records, &semicolon_opts) {
+                eprintln!("Error writing CSV: {}", err);
+            }
+        }
+        Err(err) => eprintln!("Error: {:?}", err),
+    }
+
 }
 
 #[cfg(test)]
@@ -277,7 +769,8 @@ mod tests {
     use crate::csv_parser::{
         ParseError,
         // Functions under testing
-        parse_and_get_col_from_csv, parse_and_get_line_from_csv, parse_csv, pretty_print_csv, parse_and_sum_col_from_csv
+        parse_and_get_col_from_csv, parse_and_get_line_from_csv, parse_csv, pretty_print_csv, parse_and_sum_col_from_csv,
+        CsvValue,
     };
     use std::fs;
     ///
@@ -458,7 +951,7 @@ mod tests {
         };
         let col_to_sum = 4;
         let result = parse_and_sum_col_from_csv(&csv_content, col_to_sum);
-        assert_eq!(result, Ok(2641));
+        assert_eq!(result, Ok(CsvValue::Int(2641)));
     }
 
     #[test]
@@ -474,7 +967,7 @@ mod tests {
         };
         let err_col_to_sum = 0;
         let result = parse_and_sum_col_from_csv(&csv_content, err_col_to_sum);
-        assert_eq!(result, Err(ParseError::ParseIntError("Alex".parse::<i32>().err().unwrap())));
+        assert_eq!(result, Err(ParseError::NonNumericCell("Alex".to_string())));
     }
 
     #[test]
@@ -492,4 +985,283 @@ mod tests {
         let result = parse_and_sum_col_from_csv(&csv_content, err_col_to_sum);
         assert_eq!(result,Err(ParseError::IndexOutOfBounds));
     }
+
+    ///
+    ///Tests of the quoted-field support in csv_parser::parse_csv
+    ///
+    #[test]
+    fn test_parse_csv_with_quoted_field_containing_comma() { // A quoted field may contain the delimiter without splitting the record
+        let input = "\"Smith, John\",41\nCarl,32\n";
+        let result = parse_csv(input);
+        assert_eq!(result, Ok(vec![
+            vec!["Smith, John".to_string(), "41".to_string()],
+            vec!["Carl".to_string(), "32".to_string()],
+        ]));
+    }
+
+    #[test]
+    fn test_parse_csv_with_escaped_quote_in_quoted_field() { // A doubled `""` inside a quoted field becomes a literal `"`
+        let input = "\"She said \"\"hi\"\"\",1\n";
+        let result = parse_csv(input);
+        assert_eq!(result, Ok(vec![
+            vec!["She said \"hi\"".to_string(), "1".to_string()],
+        ]));
+    }
+
+    #[test]
+    fn test_parse_csv_with_newline_inside_quoted_field() { // A quoted field may embed a literal newline without ending the record
+        let input = "\"123 Main St.\nApt 4\",2\nCarl,32\n";
+        let result = parse_csv(input);
+        assert_eq!(result, Ok(vec![
+            vec!["123 Main St.\nApt 4".to_string(), "2".to_string()],
+            vec!["Carl".to_string(), "32".to_string()],
+        ]));
+    }
+
+    #[test]
+    fn test_parse_csv_rejects_garbage_after_closing_quote() { // Stray characters right after a closing quote are an error, not a silent truncation
+        let input = "\"foo\"garbage,x\nnext,row\n";
+        let result = parse_csv(input);
+        assert_eq!(result, Err(ParseError::CsvParseError));
+    }
+
+    ///
+    ///Tests of csv_parser::CsvOptions and the `_with` functions
+    ///
+    #[test]
+    fn test_parse_csv_with_custom_delimiter() { // A non-comma delimiter (TSV) is parsed correctly
+        use crate::csv_parser::{parse_csv_with, CsvOptions};
+        let input = "Alex\tM\t41\nBert\tM\t42\n";
+        let opts = CsvOptions::new().delimiter('\t');
+        let result = parse_csv_with(input, &opts);
+        assert_eq!(result, Ok(vec![
+            vec!["Alex".to_string(), "M".to_string(), "41".to_string()],
+            vec!["Bert".to_string(), "M".to_string(), "42".to_string()],
+        ]));
+    }
+
+    #[test]
+    fn test_parse_csv_with_trim() { // Leading/trailing whitespace is stripped per field when opts.trim is set
+        use crate::csv_parser::{parse_csv_with, CsvOptions};
+        let input = " Alex , 41 \n";
+        let opts = CsvOptions::new().trim(true);
+        let result = parse_csv_with(input, &opts);
+        assert_eq!(result, Ok(vec![vec!["Alex".to_string(), "41".to_string()]]));
+    }
+
+    #[test]
+    fn test_parse_csv_with_trim_does_not_strip_quoted_field_whitespace() { // opts.trim only applies to unquoted fields; quoting preserves whitespace on purpose
+        use crate::csv_parser::{parse_csv_with, CsvOptions};
+        let input = "\" Alex \",41\n";
+        let opts = CsvOptions::new().trim(true);
+        let result = parse_csv_with(input, &opts);
+        assert_eq!(result, Ok(vec![vec![" Alex ".to_string(), "41".to_string()]]));
+    }
+
+    #[test]
+    fn test_parse_and_sum_col_from_csv_with_without_headers() { // opts.has_headers = false sums every row, not just rows after the first
+        use crate::csv_parser::{parse_and_sum_col_from_csv_with, CsvOptions};
+        let input = "10\n20\n30\n";
+        let opts = CsvOptions::new().has_headers(false);
+        let result = parse_and_sum_col_from_csv_with(input, 0, &opts);
+        assert_eq!(result, Ok(CsvValue::Int(60)));
+    }
+
+    #[test]
+    fn test_parse_and_get_col_from_csv_with_skips_header_when_set() { // opts.has_headers = true skips row 0, consistent with parse_and_sum_col_from_csv_with
+        use crate::csv_parser::{parse_and_get_col_from_csv_with, CsvOptions};
+        let input = "Name,Age\nAlex,41\nBert,42\n";
+        let opts = CsvOptions::new().has_headers(true);
+        let result = parse_and_get_col_from_csv_with(input, 0, &opts);
+        assert_eq!(result, Ok(vec!["Alex".to_string(), "Bert".to_string()]));
+    }
+
+    ///
+    ///Tests of csv_parser::parse_csv_typed and the CsvValue-based sum
+    ///
+    #[test]
+    fn test_parse_csv_typed_infers_int_float_and_text() { // Each cell is inferred into the most specific CsvValue variant
+        use crate::csv_parser::{parse_csv_typed, CsvOptions};
+        let input = "Alex,41,74.5\n";
+        let result = parse_csv_typed(input, &CsvOptions::default());
+        assert_eq!(result, Ok(vec![vec![
+            CsvValue::Text("Alex".to_string()),
+            CsvValue::Int(41),
+            CsvValue::Float(74.5),
+        ]]));
+    }
+
+    #[test]
+    fn test_parse_csv_typed_rejects_nan_and_infinity_spellings_as_text() { // "nan"/"inf"/"infinity" are textual cells, not floats
+        use crate::csv_parser::{parse_csv_typed, CsvOptions};
+        let input = "nan,infinity,inf,NaN\n";
+        let result = parse_csv_typed(input, &CsvOptions::default());
+        assert_eq!(result, Ok(vec![vec![
+            CsvValue::Text("nan".to_string()),
+            CsvValue::Text("infinity".to_string()),
+            CsvValue::Text("inf".to_string()),
+            CsvValue::Text("NaN".to_string()),
+        ]]));
+    }
+
+    #[test]
+    fn test_parse_and_sum_col_from_csv_with_sums_float_column() { // A column of decimals (e.g. heights) can now be summed
+        use crate::csv_parser::{parse_and_sum_col_from_csv_with, CsvOptions};
+        let input = "Name,Heightin\nAlex,74.5\nBert,68.5\n";
+        let opts = CsvOptions::new().has_headers(true);
+        let result = parse_and_sum_col_from_csv_with(input, 1, &opts);
+        assert_eq!(result, Ok(CsvValue::Float(143.0)));
+    }
+
+    #[test]
+    fn test_parse_and_sum_col_from_csv_with_non_numeric_cell() { // A genuinely textual cell yields a clear, specific error
+        use crate::csv_parser::{parse_and_sum_col_from_csv_with, CsvOptions};
+        let input = "Name,Sex\nAlex,M\nBert,M\n";
+        let opts = CsvOptions::new().has_headers(true);
+        let result = parse_and_sum_col_from_csv_with(input, 1, &opts);
+        assert_eq!(result, Err(ParseError::NonNumericCell("M".to_string())));
+    }
+
+    ///
+    ///Tests of csv_parser::parse_and_get_col_by_name and csv_parser::sum_col_by_name
+    ///
+    #[test]
+    fn test_parse_and_get_col_by_name_valid() { // Given a valid header, return the corresponding column
+        use crate::csv_parser::parse_and_get_col_by_name;
+        let file_path = "biostats1.csv";
+
+        let csv_content = match fs::read_to_string(file_path) {
+            Ok(content) => content,
+            Err(err) => {
+                eprintln!("Error reading the file: {}", err);
+                return;
+            }
+        };
+        let result = parse_and_get_col_by_name(&csv_content, "Sex");
+        assert_eq!(result, parse_and_get_col_from_csv(&csv_content, 1));
+    }
+
+    #[test]
+    fn test_parse_and_get_col_by_name_unknown() { // Given a header that doesn't exist, return UnknownColumn
+        use crate::csv_parser::parse_and_get_col_by_name;
+        let file_path = "biostats1.csv";
+
+        let csv_content = match fs::read_to_string(file_path) {
+            Ok(content) => content,
+            Err(err) => {
+                eprintln!("Error reading the file: {}", err);
+                return;
+            }
+        };
+        let result = parse_and_get_col_by_name(&csv_content, "Nickname");
+        assert_eq!(result, Err(ParseError::UnknownColumn("Nickname".to_string())));
+    }
+
+    #[test]
+    fn test_sum_col_by_name() { // sum_col_by_name matches summing the same column by index
+        use crate::csv_parser::sum_col_by_name;
+        let file_path = "biostats1.csv";
+
+        let csv_content = match fs::read_to_string(file_path) {
+            Ok(content) => content,
+            Err(err) => {
+                eprintln!("Error reading the file: {}", err);
+                return;
+            }
+        };
+        let result = sum_col_by_name(&csv_content, "Weightlbs");
+        assert_eq!(result, Ok(CsvValue::Int(2641)));
+    }
+
+    ///
+    ///Tests of CR/LF/CRLF record separator handling in csv_parser::parse_csv
+    ///
+    #[test]
+    fn test_parse_csv_with_crlf_line_endings() { // A Windows-style file (\r\n) doesn't leave a trailing \r on fields
+        let input = "Alex,M,41\r\nBert,M,42\r\n";
+        let result = parse_csv(input);
+        assert_eq!(result, Ok(vec![
+            vec!["Alex".to_string(), "M".to_string(), "41".to_string()],
+            vec!["Bert".to_string(), "M".to_string(), "42".to_string()],
+        ]));
+    }
+
+    #[test]
+    fn test_parse_csv_with_lone_cr_line_endings() { // A classic Mac-style file (\r only) still splits into records
+        let input = "Alex,M,41\rBert,M,42\r";
+        let result = parse_csv(input);
+        assert_eq!(result, Ok(vec![
+            vec!["Alex".to_string(), "M".to_string(), "41".to_string()],
+            vec!["Bert".to_string(), "M".to_string(), "42".to_string()],
+        ]));
+    }
+
+    ///
+    ///Tests of csv_parser::write_csv
+    ///
+    #[test]
+    fn test_write_csv_plain_fields() { // Plain fields are joined with the delimiter and the configured line ending, no quoting needed
+        use crate::csv_parser::{write_csv, CsvOptions};
+        let records = vec![
+            vec!["Alex".to_string(), "M".to_string(), "41".to_string()],
+            vec!["Bert".to_string(), "M".to_string(), "42".to_string()],
+        ];
+        let result = write_csv(&records, &CsvOptions::default());
+        assert_eq!(result, "Alex,M,41\nBert,M,42");
+    }
+
+    #[test]
+    fn test_write_csv_with_custom_line_ending_round_trips() { // A custom line ending is honored by write_csv and still reparses with parse_csv
+        use crate::csv_parser::{write_csv, CsvOptions};
+        let records = vec![
+            vec!["Alex".to_string(), "M".to_string(), "41".to_string()],
+            vec!["Bert".to_string(), "M".to_string(), "42".to_string()],
+        ];
+        let opts = CsvOptions::new().line_ending("\r\n");
+        let written = write_csv(&records, &opts);
+        assert_eq!(written, "Alex,M,41\r\nBert,M,42");
+        assert_eq!(parse_csv(&written), Ok(records));
+    }
+
+    #[test]
+    fn test_line_ending_rejects_sequences_the_parser_cant_read_back() { // A separator other than \r\n, \n, or \r is ignored, since write_csv's output would otherwise be unreadable
+        use crate::csv_parser::CsvOptions;
+        let opts = CsvOptions::new().line_ending(";;");
+        assert_eq!(opts, CsvOptions::new());
+    }
+
+    #[test]
+    fn test_write_csv_quotes_fields_needing_escaping() { // A field with the delimiter, a quote, or a newline gets RFC 4180 quoting
+        use crate::csv_parser::{write_csv, CsvOptions};
+        let records = vec![vec![
+            "Smith, John".to_string(),
+            "She said \"hi\"".to_string(),
+            "line1\nline2".to_string(),
+        ]];
+        let result = write_csv(&records, &CsvOptions::default());
+        assert_eq!(result, "\"Smith, John\",\"She said \"\"hi\"\"\",\"line1\nline2\"");
+    }
+
+    #[test]
+    fn test_write_csv_round_trips_with_parse_csv() { // parse_csv(write_csv(records)) yields back the original records
+        use crate::csv_parser::{write_csv, CsvOptions};
+        let records = vec![
+            vec!["Name".to_string(), "Note".to_string()],
+            vec!["Smith, John".to_string(), "says \"hi\"".to_string()],
+        ];
+        let opts = CsvOptions::default();
+        let written = write_csv(&records, &opts);
+        let result = parse_csv(&written);
+        assert_eq!(result, Ok(records));
+    }
+
+    #[test]
+    fn test_write_csv_to_writes_into_a_writer() { // write_csv_to writes the same bytes write_csv would return, into any Write
+        use crate::csv_parser::{write_csv_to, CsvOptions};
+        let records = vec![vec!["Alex".to_string(), "M".to_string(), "41".to_string()]];
+        let mut buffer: Vec<u8> = Vec::new();
+        let result = write_csv_to(&mut buffer, &records, &CsvOptions::default());
+        assert!(result.is_ok());
+        assert_eq!(String::from_utf8(buffer).unwrap(), "Alex,M,41");
+    }
 }